@@ -17,11 +17,15 @@ use std::{
 };
 
 use clap::Parser;
+use flate2::read::GzDecoder;
 use futures_util::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
+use sha2::{Digest, Sha256};
 use std::env;
-use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use std::fs::File as StdFile;
+use std::io::Read;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use xz2::read::XzDecoder;
 
 /// Simple downloader
 #[derive(Parser, Debug)]
@@ -41,8 +45,28 @@ struct Args {
     /// program will derive a name from the URL.
     #[arg(short, long)]
     filename: Option<String>,
+
+    /// Expected SHA-256 digest (lowercase hex) of the downloaded file. When
+    /// given, the download is verified and retried on mismatch instead of
+    /// being installed.
+    #[arg(long, value_name = "HEX")]
+    sha256: Option<String>,
+
+    /// Overwrite the destination file if it already exists and is non-empty.
+    #[arg(long)]
+    force: bool,
+
+    /// Unpack the downloaded `.tar.xz`/`.tar.gz`/`.zip` archive into the
+    /// destination directory and point the generated `ModelFile` at the
+    /// largest `*.gguf` file found inside it.
+    #[arg(long)]
+    extract: bool,
 }
 
+/// Maximum number of download attempts before giving up, each separated by
+/// an exponentially growing backoff.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 5;
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // -------------------------------------------------------------
@@ -62,53 +86,77 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let out_path = args.directory.join(file_name);
 
     // -------------------------------------------------------------
-    // Perform the HTTP GET request (streaming)
+    // Refuse to clobber an existing, non-empty destination unless the
+    // caller passed `--force`. The download itself is already written
+    // atomically (see `download_to_file`), so this only guards against
+    // overwriting a previous *completed* download by accident.
     // -------------------------------------------------------------
-    let response = reqwest::get(&args.url).await?;
-    if !response.status().is_success() {
-        return Err(format!("Failed to download: HTTP {}", response.status()).into());
-    }
-
-    // -------------------------------------------------------------
-    // Set up the progress bar (if Contentâ€‘Length is known)
-    // -------------------------------------------------------------
-    let total_size = response.content_length();
-    let pb = match total_size {
-        Some(len) => ProgressBar::new(len),
-        None => ProgressBar::new_spinner(),
-    };
-    pb.set_style(
-        ProgressStyle::with_template(
-            "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})",
-        )
-        .unwrap()
-        .progress_chars("#>-"),
-    );
-    if total_size.is_none() {
-        pb.enable_steady_tick(Duration::from_millis(100));
+    if !args.force {
+        if let Ok(metadata) = fs::metadata(&out_path) {
+            if metadata.len() > 0 {
+                return Err(format!(
+                    "'{}' already exists; pass --force to overwrite it",
+                    out_path.display()
+                )
+                .into());
+            }
+        }
+    } else {
+        // `--force` means a clean re-download: drop any `.partial`/`.meta`
+        // left behind by an earlier, possibly unrelated attempt so this run
+        // can't silently resume from stale bytes.
+        let _ = fs::remove_file(partial_path(&out_path));
+        let _ = fs::remove_file(validator_path(&out_path));
     }
 
     // -------------------------------------------------------------
-    // Write the response body to disk while streaming
+    // Perform the HTTP GET request (streaming), resuming a previous
+    // `.partial` download if one was left behind, and retrying the whole
+    // download a bounded number of times on transport errors or a SHA-256
+    // mismatch.
     // -------------------------------------------------------------
-    let mut file = File::create(&out_path).await?;
-    let mut stream = response.bytes_stream();
-    while let Some(chunk) = stream.next().await {
-        let data = chunk?;
-        file.write_all(&data).await?;
-        pb.inc(data.len() as u64);
+    let client = reqwest::Client::new();
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match download_to_file(&client, &args.url, &out_path, args.sha256.as_deref()).await {
+            Ok(()) => break,
+            Err(e) if attempt < MAX_DOWNLOAD_ATTEMPTS => {
+                let backoff = Duration::from_secs(2u64.pow(attempt - 1));
+                warning(
+                    stderr(),
+                    &format!(
+                        "Download attempt {}/{} failed: {} (retrying in {:?})",
+                        attempt, MAX_DOWNLOAD_ATTEMPTS, e, backoff
+                    ),
+                )?;
+                tokio::time::sleep(backoff).await;
+            }
+            Err(e) => return Err(e),
+        }
     }
-    file.flush().await?;
     let bin_file = out_path.display().to_string();
-    pb.finish_with_message("download complete");
     info(
         stdout(),
         &format!("Downloaded '{}' => '{}'", args.url, bin_file),
     )?;
 
+    // If requested, unpack the archive and point the ModelFile at the
+    // actual model weights instead of the archive itself.
+    let model_source = if args.extract {
+        let extracted = extract_archive(&out_path, &args.directory)?;
+        info(
+            stdout(),
+            &format!("Extracted '{}' => '{}'", bin_file, extracted.display()),
+        )?;
+        extracted
+    } else {
+        out_path.clone()
+    };
+
     // Create a ModelFile.
     let model_file = Path::new(&args.directory).join("ModelFile");
-    write_to_file(&model_file, format!("FROM {}", bin_file))?;
+    write_to_file(&model_file, format!("FROM {}", model_source.display()))?;
     info(
         stdout(),
         &format!("Successfully create file '{}'", model_file.display()),
@@ -119,7 +167,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         warning(stderr(), "Could not find 'ollama' executable in PATH")?;
         return Ok(());
     };
-    info(stdout(), &format!("Installing file {}...", bin_file))?;
+    info(
+        stdout(),
+        &format!("Installing file {}...", model_source.display()),
+    )?;
 
     match Command::new(ollama_exec.display().to_string())
         .arg("-f")
@@ -148,6 +199,343 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 // Helper utilities
 // -------------------------------------------------------------
 
+/// Returns the sibling `<out_path>.partial` path used to stage an
+/// in-progress download.
+fn partial_path(out_path: &Path) -> PathBuf {
+    let mut name = out_path.as_os_str().to_os_string();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+/// Returns the sidecar path that caches the `ETag`/`Last-Modified` validator
+/// for whatever is currently staged at `<out_path>.partial`.
+fn validator_path(out_path: &Path) -> PathBuf {
+    let mut name = partial_path(out_path).into_os_string();
+    name.push(".meta");
+    PathBuf::from(name)
+}
+
+/// Extracts a validator suitable for an `If-Range` header from a response,
+/// preferring `ETag` (strong/weak validators both work for `If-Range`) and
+/// falling back to `Last-Modified`.
+fn response_validator(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(reqwest::header::ETAG)
+        .or_else(|| response.headers().get(reqwest::header::LAST_MODIFIED))
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Downloads `url` into `out_path`, resuming from a `.partial` file left
+/// behind by a previous interrupted attempt.
+///
+/// If `<out_path>.partial` already exists, its length is sent as the start
+/// of an HTTP `Range` request, alongside an `If-Range` header built from the
+/// `ETag`/`Last-Modified` validator cached in `<out_path>.partial.meta` the
+/// last time bytes were written there. This way, if the remote resource
+/// changed since the partial download started, the server falls back to a
+/// full `200 OK` response instead of returning `206 Partial Content` for a
+/// byte range that no longer matches what's on disk -- without `If-Range`,
+/// such a response would be silently spliced onto the stale local bytes.
+/// Servers that honor the range (and agree the resource is unchanged)
+/// answer `206 Partial Content` and the download is appended onto the
+/// existing bytes; a `200 OK` means either there was nothing to resume or
+/// the resource changed, so the partial file is truncated and the download
+/// restarts from scratch. The `.partial` file is only renamed onto
+/// `out_path` once the stream has fully drained and been flushed, so a
+/// crash mid-download never leaves something at `out_path` that looks
+/// complete but isn't.
+///
+/// When `expected_sha256` is given, the file is hashed while it streams (no
+/// second pass is needed) and the resulting digest is compared against it
+/// after flushing; on mismatch the `.partial` file is deleted and an error
+/// is returned instead of renaming it into place.
+async fn download_to_file(
+    client: &reqwest::Client,
+    url: &str,
+    out_path: &Path,
+    expected_sha256: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let partial = partial_path(out_path);
+    let meta = validator_path(out_path);
+    let resume_from = fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+    let cached_validator = fs::read_to_string(&meta).ok();
+
+    let mut request = client.get(url);
+    if resume_from > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        if let Some(validator) = &cached_validator {
+            request = request.header(reqwest::header::IF_RANGE, validator);
+        }
+    }
+    let mut response = request.send().await?;
+
+    // A Range request can come back as neither 200 nor 206 -- most
+    // plausibly 416 Range Not Satisfiable, which happens when the
+    // `.partial` file already holds the complete content from a run that
+    // crashed between flush() and the final rename. Retry once without
+    // Range to get a fresh, authoritative response instead of failing
+    // outright, which (via the retry loop in `main()`) would otherwise
+    // repeat the same doomed request until the attempts are exhausted and
+    // leave the user to delete the `.partial` file by hand.
+    if resume_from > 0
+        && response.status() != reqwest::StatusCode::OK
+        && response.status() != reqwest::StatusCode::PARTIAL_CONTENT
+    {
+        response = client.get(url).send().await?;
+    }
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to download: HTTP {}", response.status()).into());
+    }
+
+    let resuming = resume_from > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let already_downloaded = if resuming { resume_from } else { 0 };
+
+    // Cache the validator this response came with so the *next* resume
+    // attempt can send it back via `If-Range`; drop any stale one if the
+    // server doesn't offer one.
+    match response_validator(&response) {
+        Some(validator) => fs::write(&meta, validator)?,
+        None => {
+            let _ = fs::remove_file(&meta);
+        }
+    }
+
+    // -------------------------------------------------------------
+    // Set up the progress bar (if Content‑Length is known)
+    // -------------------------------------------------------------
+    let total_size = response
+        .content_length()
+        .map(|len| len + already_downloaded);
+    let pb = match total_size {
+        Some(len) => ProgressBar::new(len),
+        None => ProgressBar::new_spinner(),
+    };
+    pb.set_style(
+        ProgressStyle::with_template(
+            "{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({eta})",
+        )
+        .unwrap()
+        .progress_chars("#>-"),
+    );
+    if total_size.is_none() {
+        pb.enable_steady_tick(Duration::from_millis(100));
+    }
+    pb.set_position(already_downloaded);
+
+    // -------------------------------------------------------------
+    // Write the response body to disk while streaming
+    // -------------------------------------------------------------
+    let mut file = if resuming {
+        tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&partial)
+            .await?
+    } else {
+        tokio::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&partial)
+            .await?
+    };
+
+    // If we're verifying a digest and resuming, the bytes already on disk
+    // must also be hashed so the final digest covers the whole file. Read
+    // them through a fixed-size buffer rather than slurping the whole
+    // (potentially multi-gigabyte) partial file into memory at once.
+    let mut hasher = expected_sha256.map(|_| Sha256::new());
+    if let (Some(hasher), true) = (hasher.as_mut(), resuming) {
+        let mut existing = tokio::fs::File::open(&partial).await?;
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let read = existing.read(&mut buf).await?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buf[..read]);
+        }
+    }
+
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let data = chunk?;
+        file.write_all(&data).await?;
+        if let Some(hasher) = hasher.as_mut() {
+            hasher.update(&data);
+        }
+        pb.inc(data.len() as u64);
+    }
+    file.flush().await?;
+    pb.finish_with_message("download complete");
+
+    if let Some(expected) = expected_sha256 {
+        let digest = hex::encode(hasher.unwrap().finalize());
+        if !digest.eq_ignore_ascii_case(expected) {
+            fs::remove_file(&partial)?;
+            let _ = fs::remove_file(&meta);
+            return Err(format!("SHA-256 mismatch: expected {}, got {}", expected, digest).into());
+        }
+    }
+
+    fs::rename(&partial, out_path)?;
+    let _ = fs::remove_file(&meta);
+    Ok(())
+}
+
+/// The archive formats that `extract_archive` knows how to unpack.
+enum ArchiveKind {
+    TarGz,
+    TarXz,
+    Zip,
+}
+
+/// Identifies the archive format of `path` from its extension, falling back
+/// to the file's magic bytes when the extension is missing or unusual.
+fn detect_archive_kind(path: &Path) -> Option<ArchiveKind> {
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        return Some(ArchiveKind::TarGz);
+    }
+    if name.ends_with(".tar.xz") {
+        return Some(ArchiveKind::TarXz);
+    }
+    if name.ends_with(".zip") {
+        return Some(ArchiveKind::Zip);
+    }
+
+    let mut magic = [0u8; 6];
+    let read = StdFile::open(path).ok()?.read(&mut magic).ok()?;
+    let magic = &magic[..read];
+    if magic.starts_with(&[0x1f, 0x8b]) {
+        Some(ArchiveKind::TarGz)
+    } else if magic.starts_with(&[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00]) {
+        Some(ArchiveKind::TarXz)
+    } else if magic.starts_with(&[0x50, 0x4b, 0x03, 0x04]) {
+        Some(ArchiveKind::Zip)
+    } else {
+        None
+    }
+}
+
+/// Returns `archive_path`'s file name with any recognized archive extension
+/// stripped off, for use as its extraction subdirectory name.
+fn archive_stem(archive_path: &Path) -> String {
+    let name = archive_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .into_owned();
+    let lower = name.to_lowercase();
+    for ext in [".tar.gz", ".tar.xz", ".tgz", ".zip"] {
+        if lower.ends_with(ext) {
+            return name[..name.len() - ext.len()].to_string();
+        }
+    }
+    name
+}
+
+/// Unpacks `archive_path` into an archive-scoped subdirectory of `dest_dir`
+/// and returns the path of the largest `*.gguf` file found inside it.
+///
+/// Extracting into a subdirectory (rather than straight into the shared
+/// `dest_dir`) keeps this archive's contents apart from whatever other
+/// archives (or the user) already left in `dest_dir`. The subdirectory is
+/// not emptied first, so files left there by an earlier extraction of an
+/// archive with the same name are included in the `.gguf` search.
+fn extract_archive(
+    archive_path: &Path,
+    dest_dir: &Path,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let kind = detect_archive_kind(archive_path)
+        .ok_or_else(|| format!("Unrecognized archive format: {}", archive_path.display()))?;
+
+    let mut extract_dir = dest_dir.join(archive_stem(archive_path));
+    if extract_dir == archive_path {
+        // No recognized extension to strip (the format came from the magic
+        // bytes), so the stem names the archive file itself.
+        let mut name = extract_dir.into_os_string();
+        name.push(".extracted");
+        extract_dir = PathBuf::from(name);
+    }
+    fs::create_dir_all(&extract_dir)?;
+
+    match kind {
+        ArchiveKind::TarGz => {
+            let decoder = GzDecoder::new(StdFile::open(archive_path)?);
+            tar::Archive::new(decoder).unpack(&extract_dir)?;
+        }
+        ArchiveKind::TarXz => {
+            let decoder = XzDecoder::new(StdFile::open(archive_path)?);
+            tar::Archive::new(decoder).unpack(&extract_dir)?;
+        }
+        ArchiveKind::Zip => unpack_zip(archive_path, &extract_dir)?,
+    }
+
+    find_largest_gguf(&extract_dir)?.ok_or_else(|| {
+        format!(
+            "No .gguf file found after extracting {}",
+            archive_path.display()
+        )
+        .into()
+    })
+}
+
+/// Streams the entries of a `.zip` archive onto disk under `dest_dir`,
+/// creating directories as they appear.
+fn unpack_zip(archive_path: &Path, dest_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let mut archive = zip::ZipArchive::new(StdFile::open(archive_path)?)?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(relative_path) = entry.enclosed_name() else {
+            continue;
+        };
+        let out_path = dest_dir.join(relative_path);
+        if entry.is_dir() {
+            fs::create_dir_all(&out_path)?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut out_file = StdFile::create(&out_path)?;
+        std::io::copy(&mut entry, &mut out_file)?;
+    }
+    Ok(())
+}
+
+/// Recursively walks `dir` and returns the path of the largest `*.gguf`
+/// file found, if any.
+fn find_largest_gguf(dir: &Path) -> io::Result<Option<PathBuf>> {
+    let mut largest: Option<(PathBuf, u64)> = None;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() {
+            if let Some((candidate, size)) = find_largest_gguf(&path)?.map(|p| {
+                let size = fs::metadata(&p).map(|m| m.len()).unwrap_or(0);
+                (p, size)
+            }) {
+                if largest.as_ref().is_none_or(|(_, best)| size > *best) {
+                    largest = Some((candidate, size));
+                }
+            }
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"))
+            && largest
+                .as_ref()
+                .is_none_or(|(_, best)| metadata.len() > *best)
+        {
+            largest = Some((path, metadata.len()));
+        }
+    }
+    Ok(largest.map(|(path, _)| path))
+}
+
 /// Derives a filename from a URL.
 fn derive_filename_from_url(url: &str) -> Result<String, Box<dyn std::error::Error>> {
     let parsed = url::Url::parse(url)?;
@@ -221,6 +609,64 @@ const PATH_SEP: &str = ";";
 #[cfg(not(target_os = "windows"))]
 const PATH_SEP: &str = ":";
 
+/// Expands `cmd` into itself plus one variant per `;`-separated extension in
+/// `pathext` (e.g. `.EXE;.CMD`), skipping empty entries. Pulled out of
+/// `candidate_names` so the `PATHEXT`-splitting logic can be unit-tested
+/// without depending on `cfg(target_os = "windows")` or process env vars.
+#[cfg(any(target_os = "windows", test))]
+fn pathext_candidates(cmd: &str, pathext: &str) -> Vec<String> {
+    let mut names = vec![cmd.to_string()];
+    names.extend(
+        pathext
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| format!("{cmd}{ext}")),
+    );
+    names
+}
+
+/// Returns the file-name candidates to try for `cmd` in a given `PATH`
+/// directory.
+///
+/// On Windows, executables may carry any of the extensions listed in the
+/// `PATHEXT` environment variable (falling back to the common
+/// `.COM;.EXE;.BAT;.CMD` list if it isn't set), so each extension is tried
+/// in turn alongside the bare name. On other platforms the bare name is the
+/// only candidate.
+#[cfg(target_os = "windows")]
+fn candidate_names(cmd: &str) -> Vec<String> {
+    let pathext = env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+    pathext_candidates(cmd, &pathext)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn candidate_names(cmd: &str) -> Vec<String> {
+    vec![cmd.to_string()]
+}
+
+/// Returns whether `path` is a file that looks executable.
+///
+/// On Unix this also requires at least one of the owner/group/other execute
+/// bits in the file's mode, since a regular, non-executable file should not
+/// be treated as a match; whether this process itself may execute it is not
+/// checked. On other platforms any existing file counts.
+fn is_executable(path: &Path) -> bool {
+    if !path.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path)
+            .map(|m| m.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
+}
+
 /// Searches for the given command in the directories specified by the `PATH`
 /// environment variable and returns its absolute path if found.
 ///
@@ -236,21 +682,19 @@ const PATH_SEP: &str = ":";
 /// # Behavior
 ///
 /// The function reads the `PATH` variable, splits it on the platform‑specific
-/// separator (`PATH_SEP`), and iterates over each directory. For each directory it
-/// constructs a candidate path by joining the directory with `cmd`. If the
-/// candidate is a regular file, that path is returned. If no such file is found,
-/// the function returns `None`.
+/// separator (`PATH_SEP`), and iterates over each directory. For each directory
+/// it tries every name `candidate_names` returns (the bare command, plus each
+/// `PATHEXT` extension on Windows) and returns the first one that
+/// `is_executable`. If no such file is found, the function returns `None`.
 fn which(cmd: &str) -> Option<PathBuf> {
     env::var_os("PATH")?
         .to_string_lossy()
         .split(PATH_SEP)
         .find_map(|dir| {
-            let candidate = Path::new(dir).join(cmd);
-            if candidate.is_file() {
-                Some(candidate)
-            } else {
-                None
-            }
+            candidate_names(cmd)
+                .into_iter()
+                .map(|name| Path::new(dir).join(name))
+                .find(|candidate| is_executable(candidate))
         })
 }
 
@@ -265,3 +709,151 @@ pub fn warning<W: Write>(mut w: W, msg: &str) -> io::Result<()> {
 pub fn info<W: Write>(mut w: W, msg: &str) -> io::Result<()> {
     writeln!(w, "{}", Green.paint(msg))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Creates a fresh scratch directory under the system temp dir for a test.
+    fn test_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = env::temp_dir().join(format!("ollama-model-installer-test-{label}-{nanos}"));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn partial_path_appends_suffix() {
+        let out = Path::new("/tmp/models/llama.gguf");
+        assert_eq!(
+            partial_path(out),
+            PathBuf::from("/tmp/models/llama.gguf.partial")
+        );
+    }
+
+    #[test]
+    fn validator_path_is_scoped_to_the_partial_file() {
+        let out = Path::new("/tmp/models/llama.gguf");
+        assert_eq!(
+            validator_path(out),
+            PathBuf::from("/tmp/models/llama.gguf.partial.meta")
+        );
+    }
+
+    #[test]
+    fn detect_archive_kind_from_extension() {
+        assert!(matches!(
+            detect_archive_kind(Path::new("model.tar.gz")),
+            Some(ArchiveKind::TarGz)
+        ));
+        assert!(matches!(
+            detect_archive_kind(Path::new("model.tgz")),
+            Some(ArchiveKind::TarGz)
+        ));
+        assert!(matches!(
+            detect_archive_kind(Path::new("model.tar.xz")),
+            Some(ArchiveKind::TarXz)
+        ));
+        assert!(matches!(
+            detect_archive_kind(Path::new("model.zip")),
+            Some(ArchiveKind::Zip)
+        ));
+    }
+
+    #[test]
+    fn detect_archive_kind_from_magic_bytes() {
+        let dir = test_dir("magic");
+
+        let gz = dir.join("mystery-file");
+        fs::write(&gz, [0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00]).unwrap();
+        assert!(matches!(detect_archive_kind(&gz), Some(ArchiveKind::TarGz)));
+
+        let zip = dir.join("other-mystery-file");
+        fs::write(&zip, [0x50, 0x4b, 0x03, 0x04]).unwrap();
+        assert!(matches!(detect_archive_kind(&zip), Some(ArchiveKind::Zip)));
+
+        let unknown = dir.join("not-an-archive");
+        fs::write(&unknown, b"plain text").unwrap();
+        assert!(detect_archive_kind(&unknown).is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn archive_stem_strips_known_extensions() {
+        assert_eq!(archive_stem(Path::new("llama-7b.tar.gz")), "llama-7b");
+        assert_eq!(archive_stem(Path::new("llama-7b.tgz")), "llama-7b");
+        assert_eq!(archive_stem(Path::new("llama-7b.tar.xz")), "llama-7b");
+        assert_eq!(archive_stem(Path::new("llama-7b.zip")), "llama-7b");
+        assert_eq!(archive_stem(Path::new("llama-7b.bin")), "llama-7b.bin");
+    }
+
+    #[test]
+    fn find_largest_gguf_picks_the_biggest_across_subdirectories() {
+        let dir = test_dir("gguf");
+        fs::write(dir.join("small.gguf"), vec![0u8; 10]).unwrap();
+        let nested = dir.join("nested");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("big.gguf"), vec![0u8; 100]).unwrap();
+        fs::write(nested.join("ignored.txt"), vec![0u8; 1000]).unwrap();
+
+        let largest = find_largest_gguf(&dir).unwrap().unwrap();
+        assert_eq!(largest, nested.join("big.gguf"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn find_largest_gguf_returns_none_when_absent() {
+        let dir = test_dir("no-gguf");
+        fs::write(dir.join("readme.txt"), b"hello").unwrap();
+
+        assert!(find_largest_gguf(&dir).unwrap().is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn pathext_candidates_include_bare_name_and_each_extension() {
+        let names = pathext_candidates("ollama", ".EXE;.CMD;.BAT");
+        assert_eq!(
+            names,
+            vec!["ollama", "ollama.EXE", "ollama.CMD", "ollama.BAT"]
+        );
+    }
+
+    #[test]
+    fn pathext_candidates_skip_empty_entries() {
+        let names = pathext_candidates("ollama", ".EXE;;.CMD");
+        assert_eq!(names, vec!["ollama", "ollama.EXE", "ollama.CMD"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn is_executable_respects_mode_bits() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = test_dir("exec");
+        let file = dir.join("maybe-exec");
+        fs::write(&file, b"#!/bin/sh\n").unwrap();
+
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(!is_executable(&file));
+
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(is_executable(&file));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn is_executable_is_false_for_missing_file() {
+        assert!(!is_executable(Path::new(
+            "/nonexistent/path/to/nothing-here"
+        )));
+    }
+}